@@ -0,0 +1,121 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2024 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::io::IsTerminal;
+
+/// The `--color` option: `auto` (the default) decides based on the output
+/// stream and environment, while `always`/`never` force the decision.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// Resolves this mode to a plain on/off decision, so the rest of the
+    /// logging code keeps dealing with a simple bool. The logger constructors
+    /// call this each time they build a logger, so `auto` is evaluated once
+    /// per logger rather than once per run.
+    ///
+    /// `auto` honors the `NO_COLOR` and `CLICOLOR`/`CLICOLOR_FORCE`
+    /// conventions before falling back to whether stderr is a TTY. This
+    /// prevents ANSI escape sequences from corrupting piped/redirected
+    /// output while still giving color on interactive terminals.
+    pub fn is_color_enabled(self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => resolve_auto(
+                std::env::var_os("NO_COLOR"),
+                std::env::var_os("CLICOLOR_FORCE"),
+                std::env::var_os("CLICOLOR"),
+                std::io::stderr().is_terminal(),
+            ),
+        }
+    }
+}
+
+/// The `auto` precedence, as a pure function of the three conventions plus
+/// TTY detection, so the branchy logic can be unit tested without touching
+/// real process environment variables.
+///
+/// `NO_COLOR` disables color when present, regardless of its value
+/// (https://no-color.org); `CLICOLOR_FORCE`/`CLICOLOR` follow the common
+/// convention where only a literal `0` means "off".
+fn resolve_auto(
+    no_color: Option<std::ffi::OsString>,
+    clicolor_force: Option<std::ffi::OsString>,
+    clicolor: Option<std::ffi::OsString>,
+    is_terminal: bool,
+) -> bool {
+    if no_color.is_some() {
+        false
+    } else if is_set(clicolor_force) {
+        true
+    } else if is_set_to_zero(clicolor) {
+        false
+    } else {
+        is_terminal
+    }
+}
+
+fn is_set(value: Option<std::ffi::OsString>) -> bool {
+    value.is_some_and(|v| v != "0")
+}
+
+fn is_set_to_zero(value: Option<std::ffi::OsString>) -> bool {
+    value.is_some_and(|v| v == "0")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn some(value: &str) -> Option<std::ffi::OsString> {
+        Some(value.into())
+    }
+
+    #[test]
+    fn no_color_wins_regardless_of_value_or_terminal() {
+        assert!(!resolve_auto(some("1"), None, None, true));
+        assert!(!resolve_auto(some("0"), None, None, true));
+        assert!(!resolve_auto(some(""), None, None, true));
+    }
+
+    #[test]
+    fn clicolor_force_wins_over_clicolor_and_non_terminal() {
+        assert!(resolve_auto(None, some("1"), some("0"), false));
+    }
+
+    #[test]
+    fn clicolor_force_set_to_zero_does_not_force_color() {
+        assert!(!resolve_auto(None, some("0"), None, false));
+    }
+
+    #[test]
+    fn clicolor_zero_disables_color_on_a_terminal() {
+        assert!(!resolve_auto(None, None, some("0"), true));
+    }
+
+    #[test]
+    fn falls_back_to_is_terminal_when_nothing_is_set() {
+        assert!(resolve_auto(None, None, None, true));
+        assert!(!resolve_auto(None, None, None, false));
+    }
+}
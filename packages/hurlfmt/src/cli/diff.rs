@@ -0,0 +1,192 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2024 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use colored::*;
+use hurl_core::text::Format;
+
+/// One line of a computed diff: unchanged context, or removed/added.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Computes a line-by-line diff between `original` and `corrected`, keeping
+/// unchanged lines as context around the removed/added ones.
+pub fn diff_lines(original: &str, corrected: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = original.lines().collect();
+    let b: Vec<&str> = corrected.lines().collect();
+    let lcs = longest_common_subsequence(&a, &b);
+
+    let mut result = vec![];
+    let (mut i, mut j, mut k) = (0, 0, 0);
+    while i < a.len() || j < b.len() {
+        if k < lcs.len() && i < a.len() && j < b.len() && a[i] == lcs[k] && b[j] == lcs[k] {
+            result.push(DiffLine::Context(a[i].to_string()));
+            i += 1;
+            j += 1;
+            k += 1;
+        } else if i < a.len() && (k >= lcs.len() || a[i] != lcs[k]) {
+            result.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    result
+}
+
+/// Classic dynamic-programming LCS: good enough for the file sizes hurlfmt
+/// deals with, and keeps us dependency-free.
+fn longest_common_subsequence<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<&'a str> {
+    let (n, m) = (a.len(), b.len());
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut lcs = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            lcs.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    lcs
+}
+
+/// Renders a diff as plain unified-diff text: `---`/`+++` headers, then
+/// context lines prefixed with a space and removed/added lines with `-`/`+`.
+pub fn unified(name: &str, original: &str, corrected: &str) -> String {
+    let mut out = format!("--- {name}\n+++ {name}\n");
+    for line in diff_lines(original, corrected) {
+        match line {
+            DiffLine::Context(l) => out.push_str(&format!(" {l}\n")),
+            DiffLine::Removed(l) => out.push_str(&format!("-{l}\n")),
+            DiffLine::Added(l) => out.push_str(&format!("+{l}\n")),
+        }
+    }
+    out
+}
+
+/// Renders a diff the same way [`super::logger::log_error`] renders a caret:
+/// a blank `gutter_width`-wide gutter column, context lines prefixed with a
+/// space, and removed/added lines prefixed with `-`/`+` and styled red/green
+/// as a whole line.
+pub fn render_unified(original: &str, corrected: &str, format: Format, gutter_width: usize) -> String {
+    let gutter = " ".repeat(gutter_width);
+    let mut out = String::new();
+    for line in diff_lines(original, corrected) {
+        let rendered = match line {
+            DiffLine::Context(l) => format!("{gutter} |   {l}"),
+            DiffLine::Removed(l) => {
+                let marker = style_line(format!("- {l}"), format, Color::Red);
+                format!("{gutter} | {marker}")
+            }
+            DiffLine::Added(l) => {
+                let marker = style_line(format!("+ {l}"), format, Color::Green);
+                format!("{gutter} | {marker}")
+            }
+        };
+        out.push_str(&rendered);
+        out.push('\n');
+    }
+    out
+}
+
+enum Color {
+    Red,
+    Green,
+}
+
+fn style_line(text: String, format: Format, color: Color) -> String {
+    if format != Format::Ansi {
+        return text;
+    }
+    match color {
+        Color::Red => text.red().to_string(),
+        Color::Green => text.green().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines_keeps_unchanged_lines_as_context() {
+        let original = "a\nb\nc";
+        let corrected = "a\nb\nc";
+        assert_eq!(
+            diff_lines(original, corrected),
+            vec![
+                DiffLine::Context("a".to_string()),
+                DiffLine::Context("b".to_string()),
+                DiffLine::Context("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_lines_reports_a_single_line_replacement() {
+        let original = "a\nb\nc";
+        let corrected = "a\nB\nc";
+        assert_eq!(
+            diff_lines(original, corrected),
+            vec![
+                DiffLine::Context("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Added("B".to_string()),
+                DiffLine::Context("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_lines_handles_pure_insertion_and_deletion() {
+        let original = "a\nc";
+        let corrected = "a\nb\nc";
+        assert_eq!(
+            diff_lines(original, corrected),
+            vec![
+                DiffLine::Context("a".to_string()),
+                DiffLine::Added("b".to_string()),
+                DiffLine::Context("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unified_renders_standard_diff_markers() {
+        let out = unified("a.hurl", "foo\nbar", "foo\nbaz");
+        assert_eq!(out, "--- a.hurl\n+++ a.hurl\n foo\n-bar\n+baz\n");
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2024 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cli::diff;
+use crate::linter;
+
+/// What hurlfmt does with a linted/formatted file, beyond just reporting
+/// problems.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmitMode {
+    /// Print the corrected content to stdout.
+    #[default]
+    Stdout,
+    /// Don't write anything; only report whether the file has lint errors.
+    Check,
+    /// Print a unified diff of original vs. corrected content.
+    Diff,
+    /// Write the corrected content back to the file, in place.
+    Replace,
+}
+
+/// Options controlling [`emit`]: the mode, and whether `Replace` keeps a
+/// `.bk` copy of the original file before overwriting it.
+#[derive(Clone, Debug, Default)]
+pub struct EmitOptions {
+    pub mode: EmitMode,
+    pub backup: bool,
+}
+
+/// Outcome of an [`emit`] call, used by the caller to decide the process
+/// exit code.
+pub struct EmitStatus {
+    pub has_errors: bool,
+}
+
+/// Emits the result of linting/formatting `path` according to `options`.
+///
+/// `original` and `corrected` are the file's content before and after the
+/// fixes implied by `lint_errors`; `corrected` is unused in `Check` mode, and
+/// `Stdout`/`Diff` modes never touch the file on disk.
+pub fn emit(
+    path: &Path,
+    original: &str,
+    corrected: &str,
+    lint_errors: &[linter::Error],
+    options: &EmitOptions,
+) -> io::Result<EmitStatus> {
+    match options.mode {
+        EmitMode::Check => Ok(EmitStatus {
+            has_errors: !lint_errors.is_empty(),
+        }),
+        EmitMode::Stdout => {
+            print!("{corrected}");
+            Ok(EmitStatus { has_errors: false })
+        }
+        EmitMode::Diff => {
+            print!("{}", diff::unified(&path.display().to_string(), original, corrected));
+            Ok(EmitStatus { has_errors: false })
+        }
+        EmitMode::Replace => {
+            if options.backup {
+                fs::copy(path, backup_path(path))?;
+            }
+            replace_atomically(path, corrected)?;
+            Ok(EmitStatus { has_errors: false })
+        }
+    }
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bk");
+    PathBuf::from(backup)
+}
+
+/// Writes `content` to a temp file next to `path`, then renames it into
+/// place, so a crash or interrupt never leaves `path` half-written.
+fn replace_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_owned();
+    tmp_name.push(".hurlfmt-tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A path under the OS temp dir, unique per test and per process so
+    /// concurrent test runs don't collide.
+    fn scratch_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hurlfmt-emit-test-{}-{test_name}", std::process::id()))
+    }
+
+    struct ScratchFile(PathBuf);
+
+    impl ScratchFile {
+        fn new(test_name: &str, content: &str) -> ScratchFile {
+            let path = scratch_path(test_name);
+            fs::write(&path, content).unwrap();
+            ScratchFile(path)
+        }
+    }
+
+    impl Drop for ScratchFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+            let _ = fs::remove_file(backup_path(&self.0));
+        }
+    }
+
+    #[test]
+    fn replace_without_backup_round_trips_content_and_writes_no_backup() {
+        let file = ScratchFile::new("replace-no-backup", "original\n");
+        let options = EmitOptions {
+            mode: EmitMode::Replace,
+            backup: false,
+        };
+        let status = emit(&file.0, "original\n", "corrected\n", &[], &options).unwrap();
+        assert!(!status.has_errors);
+        assert_eq!(fs::read_to_string(&file.0).unwrap(), "corrected\n");
+        assert!(!backup_path(&file.0).exists());
+    }
+
+    #[test]
+    fn replace_with_backup_keeps_the_original_byte_identical() {
+        let file = ScratchFile::new("replace-with-backup", "original\n");
+        let options = EmitOptions {
+            mode: EmitMode::Replace,
+            backup: true,
+        };
+        emit(&file.0, "original\n", "corrected\n", &[], &options).unwrap();
+        assert_eq!(fs::read_to_string(&file.0).unwrap(), "corrected\n");
+        assert_eq!(fs::read(backup_path(&file.0)).unwrap(), b"original\n");
+    }
+
+    #[test]
+    fn check_mode_reports_errors_without_writing_anything() {
+        let file = ScratchFile::new("check-mode", "original\n");
+        let options = EmitOptions {
+            mode: EmitMode::Check,
+            backup: false,
+        };
+        let fake_error = linter::Error {
+            source_info: hurl_core::ast::SourceInfo {
+                start: hurl_core::ast::Pos { line: 1, column: 1 },
+                end: hurl_core::ast::Pos { line: 1, column: 2 },
+            },
+            kind: linter::ErrorKind::HardTab,
+        };
+        let status = emit(&file.0, "original\n", "corrected\n", std::slice::from_ref(&fake_error), &options).unwrap();
+        assert!(status.has_errors);
+        assert_eq!(fs::read_to_string(&file.0).unwrap(), "original\n");
+        assert!(!backup_path(&file.0).exists());
+    }
+
+    #[test]
+    fn check_mode_has_no_errors_for_a_clean_file() {
+        let file = ScratchFile::new("check-mode-clean", "original\n");
+        let options = EmitOptions {
+            mode: EmitMode::Check,
+            backup: false,
+        };
+        let status = emit(&file.0, "original\n", "original\n", &[], &options).unwrap();
+        assert!(!status.has_errors);
+    }
+}
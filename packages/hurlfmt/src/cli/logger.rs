@@ -17,6 +17,9 @@
  */
 use std::path::PathBuf;
 
+use crate::cli::color::ColorMode;
+use crate::cli::diagnostic::{DiagnosticCollector, OutputFormat};
+use crate::cli::diff;
 use crate::linter;
 use colored::*;
 use hurl_core::error::DisplaySourceError;
@@ -27,30 +30,77 @@ pub fn make_logger_verbose(verbose: bool) -> impl Fn(&str) {
     move |message| log_verbose(verbose, message)
 }
 
-pub fn make_logger_error_message(color: bool) -> impl Fn(bool, &str) {
+pub fn make_logger_error_message(color: ColorMode) -> impl Fn(bool, &str) {
+    let color = color.is_color_enabled();
     move |warning, message| log_error_message(color, warning, message)
 }
 
+/// Builds the parser-error logger for one file.
+///
+/// `collector` is shared across every file of the run (build it once with
+/// [`DiagnosticCollector::new`] before looping over input files, and call
+/// `collector.flush()` exactly once after the last file) so that
+/// `Json`/`Checkstyle` output is one document for the whole run rather than
+/// one per file.
 pub fn make_logger_parser_error(
     lines: Vec<String>,
-    color: bool,
+    color: ColorMode,
     filename: Option<PathBuf>,
+    format: OutputFormat,
+    collector: &DiagnosticCollector,
 ) -> impl Fn(&parser::ParseError, bool) {
+    let color = color.is_color_enabled();
+    let collector = collector.clone();
     move |error: &parser::ParseError, warning: bool| {
-        log_error(lines.clone(), color, filename.clone(), error, warning);
+        log_error(lines.clone(), color, filename.clone(), error, warning, format, &collector);
     }
 }
 
+/// Same contract as [`make_logger_parser_error`], for linter findings. Also
+/// prints the autofix diff preview (see [`log_linter_error_diff`]) for
+/// `Human` output when `show_diff` is set.
 pub fn make_logger_linter_error(
     lines: Vec<String>,
-    color: bool,
+    color: ColorMode,
     filename: Option<PathBuf>,
+    format: OutputFormat,
+    show_diff: bool,
+    collector: &DiagnosticCollector,
 ) -> impl Fn(&linter::Error, bool) {
+    let color = color.is_color_enabled();
+    let collector = collector.clone();
     move |error: &linter::Error, warning: bool| {
-        log_error(lines.clone(), color, filename.clone(), error, warning);
+        log_error(lines.clone(), color, filename.clone(), error, warning, format, &collector);
+        if show_diff && format == OutputFormat::Human {
+            log_linter_error_diff(&lines, color, error);
+        }
     }
 }
 
+/// Prints a unified diff of the line `error` points at vs. its autofixed
+/// version, so users can preview what `--emit replace` would change before
+/// running it. Only fires for findings with a deterministic, in-place fix.
+fn log_linter_error_diff(lines: &[String], color: bool, error: &linter::Error) {
+    let line_number_size = if lines.len() < 100 {
+        2
+    } else if lines.len() < 1000 {
+        3
+    } else {
+        4
+    };
+    let format = if color { Format::Ansi } else { Format::Plain };
+
+    let line_number = error.source_info().start.line;
+    let Some(original) = lines.get(line_number - 1) else {
+        return;
+    };
+    let Some(corrected) = error.corrected_line(original) else {
+        return;
+    };
+    eprint!("{}", diff::render_unified(original, &corrected, format, line_number_size));
+    eprintln!("{} |\n", " ".repeat(line_number_size));
+}
+
 pub fn log_info(message: &str) {
     eprintln!("{message}");
 }
@@ -81,7 +131,15 @@ fn log_error(
     filename: Option<PathBuf>,
     error: &dyn DisplaySourceError,
     warning: bool,
+    format: OutputFormat,
+    collector: &DiagnosticCollector,
 ) {
+    if format != OutputFormat::Human {
+        let name = filename.map(|f| f.display().to_string()).unwrap_or_default();
+        collector.push(&name, error, warning);
+        return;
+    }
+
     let line_number_size = if lines.len() < 100 {
         2
     } else if lines.len() < 1000 {
@@ -0,0 +1,391 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2024 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::HashSet;
+
+use hurl_core::ast::{Pos, SourceInfo};
+use hurl_core::error::DisplaySourceError;
+use hurl_core::text::StyledString;
+
+/// Directive prefix used to suppress one or more rules on the line that
+/// follows it, e.g. `# hurlfmt-ignore: line-length, hard-tab`.
+const IGNORE_DIRECTIVE_PREFIX: &str = "# hurlfmt-ignore:";
+
+/// A style rule the linter can check, on top of Hurl grammar validity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rule {
+    LineLength,
+    HardTab,
+    TrailingWhitespace,
+    BareCr,
+    TodoMarker,
+}
+
+impl Rule {
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::LineLength => "line-length",
+            Rule::HardTab => "hard-tab",
+            Rule::TrailingWhitespace => "trailing-whitespace",
+            Rule::BareCr => "bare-cr",
+            Rule::TodoMarker => "todo-marker",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Rule> {
+        [
+            Rule::LineLength,
+            Rule::HardTab,
+            Rule::TrailingWhitespace,
+            Rule::BareCr,
+            Rule::TodoMarker,
+        ]
+        .into_iter()
+        .find(|rule| rule.name() == name)
+    }
+}
+
+/// Which style rules are active, and the parameters of the ones that need
+/// them. Projects opt in/out per check rather than getting an all-or-nothing
+/// linter.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub rules: Vec<Rule>,
+    pub max_line_length: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            rules: vec![
+                Rule::LineLength,
+                Rule::HardTab,
+                Rule::TrailingWhitespace,
+                Rule::BareCr,
+                Rule::TodoMarker,
+            ],
+            max_line_length: 100,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    LineTooLong { max: usize, actual: usize },
+    HardTab,
+    TrailingWhitespace,
+    BareCr,
+    TodoMarker { marker: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct Error {
+    pub source_info: SourceInfo,
+    pub kind: ErrorKind,
+}
+
+impl DisplaySourceError for Error {
+    fn source_info(&self) -> SourceInfo {
+        self.source_info
+    }
+
+    fn description(&self) -> String {
+        match &self.kind {
+            ErrorKind::LineTooLong { max, actual } => {
+                format!("line is {actual} characters long, exceeding the {max} limit")
+            }
+            ErrorKind::HardTab => "hard tab character".to_string(),
+            ErrorKind::TrailingWhitespace => "trailing whitespace".to_string(),
+            ErrorKind::BareCr => "bare carriage return character".to_string(),
+            ErrorKind::TodoMarker { marker } => format!("leftover {marker} marker"),
+        }
+    }
+
+    fn fixme(&self, _lines: &[&str]) -> StyledString {
+        let message = match &self.kind {
+            ErrorKind::LineTooLong { max, .. } => format!("split this line (max {max} characters)"),
+            ErrorKind::HardTab => "replace this tab with spaces".to_string(),
+            ErrorKind::TrailingWhitespace => "remove the trailing whitespace".to_string(),
+            ErrorKind::BareCr => "remove the bare carriage return".to_string(),
+            ErrorKind::TodoMarker { marker } => format!("resolve or remove this {marker}"),
+        };
+        StyledString::from(message)
+    }
+}
+
+impl Error {
+    /// The autofixed version of `line`, when this finding has a deterministic,
+    /// in-place textual fix. `LineTooLong` and `TodoMarker` have no such fix
+    /// (splitting a line or resolving a TODO needs a human), so they return
+    /// `None`.
+    pub fn corrected_line(&self, line: &str) -> Option<String> {
+        match &self.kind {
+            ErrorKind::HardTab => Some(line.replace('\t', "    ")),
+            ErrorKind::TrailingWhitespace => Some(line.trim_end_matches([' ', '\t']).to_string()),
+            ErrorKind::BareCr => Some(line.replace('\r', "")),
+            ErrorKind::LineTooLong { .. } | ErrorKind::TodoMarker { .. } => None,
+        }
+    }
+}
+
+/// Runs every active rule of `config` against `lines`, honoring any
+/// `hurlfmt-ignore` directive placed on the line right before a finding.
+pub fn check(lines: &[String], config: &Config) -> Vec<Error> {
+    let mut errors = vec![];
+    let mut suppressed: HashSet<Rule> = HashSet::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let line_number = i + 1;
+        let ignored_here = std::mem::take(&mut suppressed);
+
+        if let Some(rules) = parse_ignore_directive(line) {
+            suppressed = rules;
+            continue;
+        }
+
+        for rule in &config.rules {
+            if ignored_here.contains(rule) {
+                continue;
+            }
+            if let Some(error) = check_rule(*rule, line, line_number, config) {
+                errors.push(error);
+            }
+        }
+    }
+    errors
+}
+
+fn parse_ignore_directive(line: &str) -> Option<HashSet<Rule>> {
+    let rest = line.trim().strip_prefix(IGNORE_DIRECTIVE_PREFIX)?;
+    Some(rest.split(',').filter_map(|name| Rule::from_name(name.trim())).collect())
+}
+
+fn check_rule(rule: Rule, line: &str, line_number: usize, config: &Config) -> Option<Error> {
+    match rule {
+        Rule::LineLength => {
+            let actual = line.chars().count();
+            if actual <= config.max_line_length {
+                return None;
+            }
+            let source_info = source_info_for(line_number, config.max_line_length + 1, actual + 1);
+            Some(Error {
+                source_info,
+                kind: ErrorKind::LineTooLong {
+                    max: config.max_line_length,
+                    actual,
+                },
+            })
+        }
+        Rule::HardTab => {
+            let column = char_column(line, line.find('\t')?);
+            let source_info = source_info_for(line_number, column, column + 1);
+            Some(Error {
+                source_info,
+                kind: ErrorKind::HardTab,
+            })
+        }
+        Rule::TrailingWhitespace => {
+            let trimmed = line.trim_end_matches([' ', '\t']);
+            if trimmed.len() == line.len() {
+                return None;
+            }
+            let source_info = source_info_for(line_number, trimmed.chars().count() + 1, line.chars().count() + 1);
+            Some(Error {
+                source_info,
+                kind: ErrorKind::TrailingWhitespace,
+            })
+        }
+        Rule::BareCr => {
+            let column = char_column(line, line.find('\r')?);
+            let source_info = source_info_for(line_number, column, column + 1);
+            Some(Error {
+                source_info,
+                kind: ErrorKind::BareCr,
+            })
+        }
+        Rule::TodoMarker => {
+            // Only look inside the comment part of the line (Hurl comments
+            // start with `#`), and only at whole-word matches, so ordinary
+            // words like "MASTODON" or "AUTODOC" don't trip the check.
+            let comment_start = line.find('#')?;
+            let comment = &line[comment_start..];
+            for marker in ["TODO", "FIXME"] {
+                if let Some(rel_index) = find_word(comment, marker) {
+                    let index = comment_start + rel_index;
+                    let column = char_column(line, index);
+                    let marker_len = marker.chars().count();
+                    let source_info = source_info_for(line_number, column, column + marker_len);
+                    return Some(Error {
+                        source_info,
+                        kind: ErrorKind::TodoMarker {
+                            marker: marker.to_string(),
+                        },
+                    });
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Finds the first occurrence of `word` in `haystack` that isn't glued to
+/// another identifier character on either side.
+fn find_word(haystack: &str, word: &str) -> Option<usize> {
+    haystack.match_indices(word).find_map(|(index, _)| {
+        let before_ok = haystack[..index].chars().next_back().map_or(true, |c| !is_word_char(c));
+        let after_ok = haystack[index + word.len()..].chars().next().map_or(true, |c| !is_word_char(c));
+        (before_ok && after_ok).then_some(index)
+    })
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Converts a byte offset from `str::find` into the 1-based char column the
+/// caret/diff renderers expect, so multi-byte UTF-8 content before the match
+/// doesn't throw the reported column off.
+fn char_column(line: &str, byte_index: usize) -> usize {
+    line[..byte_index].chars().count() + 1
+}
+
+fn source_info_for(line: usize, start_column: usize, end_column: usize) -> SourceInfo {
+    SourceInfo {
+        start: Pos { line, column: start_column },
+        end: Pos { line, column: end_column },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn kinds(errors: &[Error]) -> Vec<ErrorKind> {
+        errors.iter().map(|e| e.kind.clone()).collect()
+    }
+
+    #[test]
+    fn line_length_flags_lines_over_the_limit() {
+        let config = Config {
+            rules: vec![Rule::LineLength],
+            max_line_length: 5,
+        };
+        let errors = check(&lines(&["short", "too long"]), &config);
+        assert_eq!(
+            kinds(&errors),
+            vec![ErrorKind::LineTooLong { max: 5, actual: 8 }]
+        );
+        assert_eq!(errors[0].source_info.start.line, 2);
+        assert_eq!(errors[0].source_info.start.column, 6);
+    }
+
+    #[test]
+    fn hard_tab_reports_a_char_column_past_multibyte_text() {
+        let config = Config {
+            rules: vec![Rule::HardTab],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["café\tfoo"]), &config);
+        assert_eq!(kinds(&errors), vec![ErrorKind::HardTab]);
+        // "café" is 4 chars (5 bytes): the tab is the 5th char, not the 6th byte.
+        assert_eq!(errors[0].source_info.start.column, 5);
+    }
+
+    #[test]
+    fn trailing_whitespace_is_flagged() {
+        let config = Config {
+            rules: vec![Rule::TrailingWhitespace],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["clean", "dirty   "]), &config);
+        assert_eq!(kinds(&errors), vec![ErrorKind::TrailingWhitespace]);
+        assert_eq!(errors[0].source_info.start.column, 6);
+    }
+
+    #[test]
+    fn bare_cr_reports_a_char_column_past_multibyte_text() {
+        let config = Config {
+            rules: vec![Rule::BareCr],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["café\rfoo"]), &config);
+        assert_eq!(kinds(&errors), vec![ErrorKind::BareCr]);
+        assert_eq!(errors[0].source_info.start.column, 5);
+    }
+
+    #[test]
+    fn todo_marker_is_flagged_with_its_char_span() {
+        let config = Config {
+            rules: vec![Rule::TodoMarker],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["# café TODO: fix me"]), &config);
+        assert_eq!(
+            kinds(&errors),
+            vec![ErrorKind::TodoMarker {
+                marker: "TODO".to_string()
+            }]
+        );
+        let info = errors[0].source_info;
+        assert_eq!(info.end.column - info.start.column, 4);
+    }
+
+    #[test]
+    fn todo_marker_ignores_words_that_merely_contain_the_marker() {
+        let config = Config {
+            rules: vec![Rule::TodoMarker],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["# MASTODON and AUTODOC are not TODOs"]), &config);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn todo_marker_ignores_matches_outside_a_comment() {
+        let config = Config {
+            rules: vec![Rule::TodoMarker],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["GET https://example.org/TODO"]), &config);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn ignore_directive_suppresses_only_the_next_line() {
+        let config = Config::default();
+        let errors = check(
+            &lines(&["# hurlfmt-ignore: hard-tab", "a\tb", "c\td"]),
+            &config,
+        );
+        assert_eq!(kinds(&errors), vec![ErrorKind::HardTab]);
+        assert_eq!(errors[0].source_info.start.line, 3);
+    }
+
+    #[test]
+    fn disabled_rules_are_never_checked() {
+        let config = Config {
+            rules: vec![Rule::HardTab],
+            max_line_length: 100,
+        };
+        let errors = check(&lines(&["trailing   "]), &config);
+        assert!(errors.is_empty());
+    }
+}